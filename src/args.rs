@@ -0,0 +1,188 @@
+// bread-scheme -- R7RS Scheme interpreter
+// Copyright (C) 2023 Archit Gupta <archit@accelbread.com>
+//
+// This program is free software: you can redistribute it and/or modify it under
+// the terms of the GNU Affero General Public License as published by the Free
+// Software Foundation, either version 3 of the License, or (at your option) any
+// later version.
+//
+// This program is distributed in the hope that it will be useful, but WITHOUT
+// ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or FITNESS
+// FOR A PARTICULAR PURPOSE. See the GNU Affero General Public License for more
+// details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+//
+// SPDX-License-Identifier: AGPL-3.0-or-later
+
+//! POSIX `getopt`-style command-line argument parsing.
+
+use std::sync::OnceLock;
+
+static COMMAND_LINE: OnceLock<Vec<String>> = OnceLock::new();
+
+/// Records the operands left over after option parsing so they can later be
+/// exposed to Scheme code through a `command-line` primitive.
+///
+/// Intended to be called once, early in `main`.
+pub fn set_command_line(operands: Vec<String>) {
+    COMMAND_LINE.set(operands).expect("command line set twice");
+}
+
+/// Returns the operands recorded by [`set_command_line`], or an empty slice
+/// if it was never called.
+#[must_use]
+pub fn command_line() -> &'static [String] {
+    COMMAND_LINE.get().map_or(&[], Vec::as_slice)
+}
+
+/// A recognized option and its attached value, if any.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Opt {
+    /// The option character, as it appeared in the option string.
+    pub name: char,
+    /// The option's value, present when the option string marks `name` as
+    /// argument-taking (`name:`).
+    pub arg: Option<String>,
+}
+
+/// An error produced while matching an argument vector against an option
+/// string.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GetoptError {
+    /// An option character that does not appear in the option string.
+    Unknown(char),
+    /// An argument-taking option was given no value.
+    MissingArg(char),
+}
+
+/// Splits `args` into options recognized by `optstring` and the remaining
+/// positional operands.
+///
+/// `optstring` lists the recognized option characters; a character followed
+/// by `:` takes a value, either attached to the option (`-efoo`) or taken
+/// from the next element of `args` (`-e foo`). Options may be clustered
+/// behind a single `-` (`-el` is `-e -l`), provided only the last option in
+/// the cluster takes a value. Parsing stops at a literal `--` argument (which
+/// is itself consumed) or at the first argument not starting with `-`; every
+/// argument from that point on is a positional operand.
+#[must_use]
+pub fn getopt(args: &[String], optstring: &str) -> (Vec<Result<Opt, GetoptError>>, Vec<String>) {
+    let mut opts = Vec::new();
+    let mut rest = Vec::new();
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        if arg == "--" {
+            rest.extend(iter.by_ref().cloned());
+            break;
+        }
+        if arg == "-" || !arg.starts_with('-') {
+            rest.push(arg.clone());
+            rest.extend(iter.by_ref().cloned());
+            break;
+        }
+        let mut chars = arg[1..].chars();
+        while let Some(c) = chars.next() {
+            if !takes_arg(optstring, c) {
+                if optstring.contains(c) {
+                    opts.push(Ok(Opt { name: c, arg: None }));
+                } else {
+                    opts.push(Err(GetoptError::Unknown(c)));
+                }
+                continue;
+            }
+            let attached: String = chars.by_ref().collect();
+            let value = if attached.is_empty() {
+                iter.next().cloned()
+            } else {
+                Some(attached)
+            };
+            opts.push(match value {
+                Some(arg) => Ok(Opt { name: c, arg: Some(arg) }),
+                None => Err(GetoptError::MissingArg(c)),
+            });
+            break;
+        }
+    }
+    (opts, rest)
+}
+
+/// Returns whether `optstring` marks `c` as an option that takes a value.
+fn takes_arg(optstring: &str, c: char) -> bool {
+    optstring
+        .char_indices()
+        .find(|&(_, ch)| ch == c)
+        .is_some_and(|(i, _)| optstring[i + c.len_utf8()..].starts_with(':'))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(v: &[&str]) -> Vec<String> {
+        v.iter().map(|s| (*s).to_string()).collect()
+    }
+
+    #[test]
+    fn no_options() {
+        let (opts, rest) = getopt(&args(&["foo.scm", "bar"]), "e:l:");
+        assert!(opts.is_empty());
+        assert_eq!(rest, args(&["foo.scm", "bar"]));
+    }
+
+    #[test]
+    fn simple_flag() {
+        let (opts, rest) = getopt(&args(&["-e", "(+ 1 2)"]), "e:l:");
+        assert_eq!(opts, vec![Ok(Opt { name: 'e', arg: Some("(+ 1 2)".to_string()) })]);
+        assert!(rest.is_empty());
+    }
+
+    #[test]
+    fn attached_value() {
+        let (opts, rest) = getopt(&args(&["-e(+ 1 2)"]), "e:l:");
+        assert_eq!(opts, vec![Ok(Opt { name: 'e', arg: Some("(+ 1 2)".to_string()) })]);
+        assert!(rest.is_empty());
+    }
+
+    #[test]
+    fn clustering() {
+        let (opts, rest) = getopt(&args(&["-vl", "foo.scm"]), "vl:");
+        assert_eq!(
+            opts,
+            vec![
+                Ok(Opt { name: 'v', arg: None }),
+                Ok(Opt { name: 'l', arg: Some("foo.scm".to_string()) })
+            ]
+        );
+        assert!(rest.is_empty());
+    }
+
+    #[test]
+    fn missing_arg() {
+        let (opts, rest) = getopt(&args(&["-e"]), "e:l:");
+        assert_eq!(opts, vec![Err(GetoptError::MissingArg('e'))]);
+        assert!(rest.is_empty());
+    }
+
+    #[test]
+    fn unknown_option() {
+        let (opts, rest) = getopt(&args(&["-z"]), "e:l:");
+        assert_eq!(opts, vec![Err(GetoptError::Unknown('z'))]);
+        assert!(rest.is_empty());
+    }
+
+    #[test]
+    fn double_dash_stops_parsing() {
+        let (opts, rest) = getopt(&args(&["--", "-e"]), "e:l:");
+        assert!(opts.is_empty());
+        assert_eq!(rest, args(&["-e"]));
+    }
+
+    #[test]
+    fn script_and_trailing_args() {
+        let (opts, rest) = getopt(&args(&["-e", "1", "foo.scm", "a", "b"]), "e:l:");
+        assert_eq!(opts, vec![Ok(Opt { name: 'e', arg: Some("1".to_string()) })]);
+        assert_eq!(rest, args(&["foo.scm", "a", "b"]));
+    }
+}
@@ -19,70 +19,103 @@
 #![allow(clippy::vec_box)]
 
 use std::{
-    io::{BufReader, ErrorKind, Read},
+    io::{self, BufReader, ErrorKind, Read},
     slice,
 };
 
-pub struct Input<'a, S: Read> {
-    stream: BufReader<&'a mut S>,
+/// A byte stream with a small pushback buffer, as needed to re-read a
+/// just-consumed delimiter byte, or the up-to-four bytes of a just-decoded
+/// UTF-8 character (see `ports::peek_char`).
+///
+/// `Input` owns its stream rather than borrowing it, so one can be stored
+/// for as long as needed (e.g. inside a [`Port`](crate::types::Port)) instead
+/// of living only as long as a single call into the reader.
+pub struct Input<S: Read> {
+    stream: BufReader<S>,
     buffered: usize,
-    buf: [u8; 2],
+    buf: [u8; 4],
+    pos: usize,
 }
 
-impl<'a, S: Read> Input<'a, S> {
-    pub fn new(stream: &'a mut S) -> Self {
+impl<S: Read> Input<S> {
+    pub fn new(stream: S) -> Self {
         Self {
             stream: BufReader::new(stream),
             buffered: 0,
-            buf: [0, 0],
+            buf: [0; 4],
+            pos: 0,
         }
     }
 
-    pub fn get(&mut self) -> Option<u8> {
+    /// Returns the next byte of input, or `Ok(None)` at end of stream.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the underlying stream fails to read.
+    pub fn get(&mut self) -> io::Result<Option<u8>> {
         if self.buffered > 0 {
             let c = self.buf[0];
             self.buffered -= 1;
-            self.buf[0] = std::mem::take(&mut self.buf[1]);
-            Some(c)
+            self.buf.copy_within(1..=self.buffered, 0);
+            self.pos += 1;
+            Ok(Some(c))
         } else {
             let mut c = 0u8;
             match self.stream.read_exact(slice::from_mut(&mut c)) {
-                Ok(()) => Some(c),
+                Ok(()) => {
+                    self.pos += 1;
+                    Ok(Some(c))
+                }
                 Err(e) => match e.kind() {
-                    ErrorKind::UnexpectedEof => None,
-                    _ => panic!("Input error: {e}"),
+                    ErrorKind::UnexpectedEof => Ok(None),
+                    _ => Err(e),
                 },
             }
         }
     }
 
+    /// Pushes `byte` back so the next [`get`](Self::get) returns it again.
+    ///
+    /// Pushing multiple bytes without an intervening `get` replays them in
+    /// last-pushed-first order, i.e. push the bytes of a multi-byte sequence
+    /// in reverse so they read back out in their original order.
     pub fn push(&mut self, byte: u8) {
         assert!(
             self.buffered < self.buf.len(),
             "Pushing byte onto input with no space."
         );
 
-        self.buffered += 1;
-        self.buf[1] = self.buf[0];
+        self.buf.copy_within(0..self.buffered, 1);
         self.buf[0] = byte;
+        self.buffered += 1;
+        self.pos -= 1;
+    }
+
+    /// Returns the number of bytes consumed from the stream so far, less any
+    /// pushed-back bytes.
+    #[must_use]
+    pub fn pos(&self) -> usize {
+        self.pos
     }
 
     pub fn has_pending(&self) -> bool {
         self.buffered > 0 || !self.stream.buffer().is_empty()
     }
 
-    pub fn clear_pending_space(&mut self) {
+    /// # Errors
+    ///
+    /// Returns an error if the underlying stream fails to read.
+    pub fn clear_pending_space(&mut self) -> io::Result<()> {
         while self.has_pending() {
-            let c = self.get();
-            match c {
+            match self.get()? {
                 Some(b' ') => (),
-                Some(b'\n') => return,
+                Some(b'\n') | None => return Ok(()),
                 Some(c) => {
                     self.push(c);
-                    return;
+                    return Ok(());
                 }
-                None => unreachable!(),
             };
         }
+        Ok(())
     }
 }
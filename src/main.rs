@@ -21,37 +21,140 @@
 #![warn(missing_docs, clippy::pedantic, clippy::cargo)]
 #![allow(clippy::similar_names)]
 
+mod args;
 mod eval;
 mod input;
 mod parser;
+mod ports;
 mod printer;
 mod types;
 
+use crate::args::{getopt, set_command_line, GetoptError, Opt};
 use crate::eval::eval;
 use crate::input::Input;
-use crate::parser::read;
+use crate::parser::{read, ParseError, ParseErrorKind};
+use crate::ports::PortError;
 use crate::printer::print;
-use crate::types::Object;
+use crate::types::{Handle, Object};
 use std::{
-    io::{self, Result, Write},
+    env,
+    io::{self, Write},
     process::exit,
 };
 
-fn main() -> Result<()> {
+/// Exit codes from BSD `sysexits.h`, used to report why a non-interactive
+/// run failed.
+mod sysexits {
+    /// The command was used incorrectly.
+    pub const USAGE: i32 = 64;
+    /// The input data was incorrect in some way.
+    pub const DATAERR: i32 = 65;
+    /// An input file did not exist or was not readable.
+    pub const NOINPUT: i32 = 66;
+    /// An internal software error was detected.
+    pub const SOFTWARE: i32 = 70;
+}
+
+fn main() -> io::Result<()> {
+    let argv: Vec<String> = env::args().skip(1).collect();
+    let (opts, operands) = getopt(&argv, "e:l:");
+
+    for opt in opts {
+        match opt {
+            Ok(Opt { name: 'e', arg: Some(expr) }) => {
+                let parsed = die_on_parse_error(read(&mut Input::new(expr.as_bytes())));
+                print(&eval(parsed));
+                println!();
+            }
+            Ok(Opt { name: 'l', arg: Some(path) }) => run_file(&path),
+            Ok(_) => unreachable!("optstring only declares argument-taking options"),
+            Err(GetoptError::Unknown(c)) => {
+                eprintln!("bread-scheme: unknown option -- '{c}'");
+                exit(sysexits::USAGE);
+            }
+            Err(GetoptError::MissingArg(c)) => {
+                eprintln!("bread-scheme: option requires an argument -- '{c}'");
+                exit(sysexits::USAGE);
+            }
+        }
+    }
+
+    if let Some((script, args)) = operands.split_first() {
+        set_command_line(args.to_vec());
+        run_file(script);
+        Ok(())
+    } else {
+        set_command_line(Vec::new());
+        repl()
+    }
+}
+
+/// Exits with `EX_DATAERR` or `EX_SOFTWARE` after reporting a parse failure
+/// that occurred outside the REPL, where there is no way to recover and keep
+/// going.
+fn die_on_parse_error(result: Result<Handle, ParseError>) -> Handle {
+    result.unwrap_or_else(|e| {
+        eprintln!("bread-scheme: {e}");
+        exit(match e.kind {
+            ParseErrorKind::Io(_) => sysexits::SOFTWARE,
+            _ => sysexits::DATAERR,
+        });
+    })
+}
+
+/// Exits with `EX_DATAERR` or `EX_SOFTWARE` after reporting a port failure
+/// that occurred outside the REPL, where there is no way to recover and keep
+/// going. Mirrors [`die_on_parse_error`]'s distinction: a malformed datum is
+/// `EX_DATAERR`, while a failing stream (or, for the input port that
+/// [`run_file`] opens itself, an internal misuse of the port API) is
+/// `EX_SOFTWARE`.
+fn die_on_port_error(result: Result<Handle, PortError>) -> Handle {
+    result.unwrap_or_else(|e| {
+        eprintln!("bread-scheme: {e}");
+        exit(match e {
+            PortError::Parse(ParseError { kind: ParseErrorKind::Io(_), .. }) => sysexits::SOFTWARE,
+            PortError::Parse(_) => sysexits::DATAERR,
+            PortError::Io(_) | PortError::NotAnInputPort | PortError::NotAnOutputPort => {
+                sysexits::SOFTWARE
+            }
+        });
+    })
+}
+
+/// Reads and evaluates every datum in the file at `path`, in order.
+fn run_file(path: &str) {
+    let port = ports::open_input_file(path).unwrap_or_else(|e| {
+        eprintln!("bread-scheme: {path}: {e}");
+        exit(sysexits::NOINPUT);
+    });
+    loop {
+        let parsed = die_on_port_error(ports::read_port(&port));
+        if let Object::Eof = *parsed.borrow() {
+            return;
+        }
+        eval(parsed);
+    }
+}
+
+/// Runs an interactive read-eval-print loop over stdin.
+fn repl() -> io::Result<()> {
     println!("Welcome to Bread Scheme!");
-    let mut handle = &mut io::stdin().lock();
-    let mut input = Input::new(&mut handle);
+    let mut input = Input::new(io::stdin().lock());
     loop {
         if !input.has_pending() {
             print!(">>> ");
             io::stdout().flush()?;
         }
-        let parsed = read(&mut input);
-        if let Object::Eof = *parsed.borrow() {
-            exit(0);
+        match read(&mut input) {
+            Ok(parsed) => {
+                if let Object::Eof = *parsed.borrow() {
+                    exit(0);
+                }
+                print(&eval(parsed));
+                println!();
+            }
+            Err(e) => eprintln!("bread-scheme: {e}"),
         }
-        print(&eval(parsed));
-        println!();
-        input.clear_pending_space();
+        input.clear_pending_space()?;
     }
 }
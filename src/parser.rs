@@ -18,8 +18,83 @@
 
 #![allow(clippy::vec_box)]
 
-use crate::{input::Input, types::Handle};
-use std::io::Read;
+use crate::{
+    input::Input,
+    types::{Handle, Object},
+};
+use std::{error, fmt, io::Read};
+
+/// The kind of malformed input a [`ParseError`] reports.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseErrorKind {
+    /// A `)` appeared with no matching open list.
+    UnexpectedCloseParen,
+    /// A byte that cannot start or continue any datum.
+    UnexpectedChar(u8),
+    /// The input ended in the middle of a datum.
+    UnexpectedEof,
+    /// A lone `.` appeared outside a dotted-pair tail.
+    MisplacedDot,
+    /// `.` is not a valid identifier.
+    InvalidIdentifier,
+    /// An identifier or string was not valid UTF-8.
+    InvalidUtf8,
+    /// A numeric literal does not fit in the target representation.
+    NumberOverflow,
+    /// A rational literal's denominator was zero.
+    DivisionByZero,
+    /// A `#\name` character literal or `\xHHHH;` string escape did not denote
+    /// a known character.
+    InvalidCharacterName,
+    /// The underlying input stream returned an I/O error.
+    Io(String),
+}
+
+impl fmt::Display for ParseErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnexpectedCloseParen => write!(f, "unexpected `)`"),
+            Self::UnexpectedChar(c) => write!(f, "unexpected `{}`", c.escape_ascii()),
+            Self::UnexpectedEof => write!(f, "unexpected end of input"),
+            Self::MisplacedDot => write!(f, "unexpected `.`"),
+            Self::InvalidIdentifier => write!(f, "`.` is not a valid identifier"),
+            Self::InvalidUtf8 => write!(f, "invalid UTF-8"),
+            Self::NumberOverflow => write!(f, "number literal out of range"),
+            Self::DivisionByZero => write!(f, "rational literal has a zero denominator"),
+            Self::InvalidCharacterName => write!(f, "not a known character name"),
+            Self::Io(e) => write!(f, "I/O error: {e}"),
+        }
+    }
+}
+
+/// An error produced by [`read`] when its input does not denote a valid
+/// datum.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    /// What went wrong.
+    pub kind: ParseErrorKind,
+    /// The byte offset into the input stream at which it went wrong.
+    pub offset: usize,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "parse error at byte {}: {}", self.offset, self.kind)
+    }
+}
+
+impl error::Error for ParseError {}
+
+fn err(input: &Input<impl Read>, kind: ParseErrorKind) -> ParseError {
+    ParseError { kind, offset: input.pos() }
+}
+
+fn get(input: &mut Input<impl Read>) -> Result<Option<u8>, ParseError> {
+    let offset = input.pos();
+    input
+        .get()
+        .map_err(|e| ParseError { kind: ParseErrorKind::Io(e.to_string()), offset })
+}
 
 #[derive(Default)]
 enum ParseState {
@@ -28,9 +103,23 @@ enum ParseState {
     List(Vec<Handle>),
     MaybeDot(Vec<Handle>),
     ListEnd(Vec<Handle>),
-    Int(Vec<u8>),
-    Symbol(Vec<u8>),
+    Atom(Vec<u8>),
     String(Vec<u8>),
+    /// After a string's `\`, deciding which escape it introduces.
+    StringEscape(Vec<u8>),
+    /// Collecting the hex digits of a `\xHHHH;` string escape.
+    StringHexEscape(Vec<u8>, Vec<u8>),
+    /// After a string's `\<newline>` line continuation, skipping leading
+    /// intraline whitespace on the next line.
+    StringLineContinuation(Vec<u8>),
+    /// After `#`, deciding what it introduces.
+    Hash,
+    Vector(Vec<Handle>),
+    /// After `#\`, deciding whether this is a single literal character or the
+    /// start of a multi-letter character name.
+    CharStart,
+    /// Collecting the letters of a `#\name` character literal.
+    Char(Vec<u8>),
 }
 
 fn make_list(vec: Vec<Handle>) -> Handle {
@@ -49,135 +138,610 @@ fn make_list(vec: Vec<Handle>) -> Handle {
     prev
 }
 
-fn make_symbol(vec: Vec<u8>) -> Handle {
-    assert!(
-        (vec.len() != 1) || (vec[0] != b'.'),
-        "Parse error: `.` is not a valid symbol."
-    );
-    Handle::new_symbol(
-        String::from_utf8(vec).unwrap_or_else(|e| panic!("Error parsing identifier: {e}.")),
-    )
+fn make_symbol(vec: Vec<u8>, offset: usize) -> Result<Handle, ParseError> {
+    if vec.len() == 1 && vec[0] == b'.' {
+        return Err(ParseError { kind: ParseErrorKind::InvalidIdentifier, offset });
+    }
+    let s = String::from_utf8(vec)
+        .map_err(|_| ParseError { kind: ParseErrorKind::InvalidUtf8, offset })?;
+    Ok(Handle::new_symbol(s))
 }
 
-fn make_int(mut v: &[u8]) -> Handle {
-    let mut i = 0i64;
-    let negative = v[0] == b'-';
-    if let b'-' | b'+' = v[0] {
-        v = &v[1..];
+/// A lexical recognizer: matches a prefix of `s` it accepts.
+///
+/// Implementations only need to report how much of `s` they matched; the
+/// combinators below compose them into the R7RS numeric grammar, and callers
+/// reinspect the matched text afterwards to build the actual value.
+trait Recognizer {
+    /// Returns the length of the prefix of `s` this recognizer matches, or
+    /// `None` if `s` does not start with a match. A recognizer that accepts
+    /// the empty string (such as [`star`] or [`opt`]) returns `Some(0)`
+    /// rather than `None`; [`alt`] returns its first match, not its longest.
+    fn recognize(&self, s: &[u8]) -> Option<usize>;
+}
+
+impl<F: Fn(&[u8]) -> Option<usize>> Recognizer for F {
+    fn recognize(&self, s: &[u8]) -> Option<usize> {
+        self(s)
     }
-    for c in v {
-        i = i * 10 + i64::from(c - b'0');
+}
+
+/// Matches `a` immediately followed by `b`, summing their lengths.
+fn seq<'a>(a: impl Recognizer + 'a, b: impl Recognizer + 'a) -> impl Recognizer + 'a {
+    move |s: &[u8]| {
+        let la = a.recognize(s)?;
+        let lb = b.recognize(&s[la..])?;
+        Some(la + lb)
     }
-    if negative {
-        i *= -1;
+}
+
+/// Matches whichever of `a` or `b` matches first, preferring `a`.
+fn alt<'a>(a: impl Recognizer + 'a, b: impl Recognizer + 'a) -> impl Recognizer + 'a {
+    move |s: &[u8]| a.recognize(s).or_else(|| b.recognize(s))
+}
+
+/// Matches zero or more repetitions of `a`, greedily.
+fn star<'a>(a: impl Recognizer + 'a) -> impl Recognizer + 'a {
+    move |s: &[u8]| {
+        let mut total = 0;
+        while let Some(len) = a.recognize(&s[total..]) {
+            total += len;
+        }
+        Some(total)
     }
-    Handle::new_int64(i)
 }
 
-fn make_string(vec: Vec<u8>) -> Handle {
-    Handle::new_string(
-        String::from_utf8(vec).unwrap_or_else(|e| panic!("Error parsing identifier: {e}.")),
+/// Matches `a` if present, otherwise matches zero bytes.
+fn opt<'a>(a: impl Recognizer + 'a) -> impl Recognizer + 'a {
+    move |s: &[u8]| Some(a.recognize(s).unwrap_or(0))
+}
+
+/// Matches a single literal byte, case-insensitively.
+fn lit_ci<'a>(byte: u8) -> impl Recognizer + 'a {
+    move |s: &[u8]| s.first().filter(|c| c.eq_ignore_ascii_case(&byte)).map(|_| 1)
+}
+
+fn sign() -> impl Recognizer {
+    |s: &[u8]| matches!(s.first(), Some(b'+' | b'-')).then_some(1)
+}
+
+fn digit(radix: u32) -> impl Recognizer {
+    move |s: &[u8]| s.first().filter(|c| (**c as char).is_digit(radix)).map(|_| 1)
+}
+
+fn digits(radix: u32) -> impl Recognizer {
+    seq(digit(radix), star(digit(radix)))
+}
+
+fn rational(radix: u32) -> impl Recognizer {
+    seq(digits(radix), seq(lit_ci(b'/'), digits(radix)))
+}
+
+fn exponent() -> impl Recognizer {
+    seq(alt(lit_ci(b'e'), lit_ci(b'E')), seq(opt(sign()), digits(10)))
+}
+
+/// Matches an R7RS decimal real: `digits . digits? exponent?`,
+/// `. digits exponent?`, or `digits exponent` (no decimal point at all).
+/// Only meaningful in base 10.
+fn decimal() -> impl Recognizer {
+    alt(
+        alt(
+            seq(digits(10), seq(lit_ci(b'.'), seq(opt(digits(10)), opt(exponent())))),
+            seq(lit_ci(b'.'), seq(digits(10), opt(exponent()))),
+        ),
+        seq(digits(10), exponent()),
+    )
+}
+
+/// Matches the unsigned magnitude of a number in the given radix: a
+/// rational, a decimal (base 10 only), or a plain digit run.
+fn magnitude(radix: u32) -> impl Recognizer {
+    move |s: &[u8]| {
+        if radix == 10 {
+            if let Some(len) = decimal().recognize(s) {
+                return Some(len);
+            }
+        }
+        alt(rational(radix), digits(radix)).recognize(s)
+    }
+}
+
+/// Matches a complete signed number body (without the `#`-prefix) in the
+/// given radix.
+fn number(radix: u32) -> impl Recognizer {
+    move |s: &[u8]| {
+        let signed = opt(sign()).recognize(s).unwrap_or(0);
+        let len = magnitude(radix).recognize(&s[signed..])?;
+        Some(signed + len)
+    }
+}
+
+fn radix_prefix() -> impl Recognizer {
+    seq(lit_ci(b'#'), alt(alt(lit_ci(b'x'), lit_ci(b'o')), alt(lit_ci(b'b'), lit_ci(b'd'))))
+}
+
+fn exactness_prefix() -> impl Recognizer {
+    seq(lit_ci(b'#'), alt(lit_ci(b'e'), lit_ci(b'i')))
+}
+
+/// Matches the optional `#x`/`#o`/`#b`/`#d` radix and `#e`/`#i` exactness
+/// prefixes, in either order, at most one of each.
+fn number_prefix() -> impl Recognizer {
+    alt(
+        seq(radix_prefix(), opt(exactness_prefix())),
+        seq(exactness_prefix(), opt(radix_prefix())),
     )
 }
 
+/// The exactness requested by a number's `#e`/`#i` prefix, if any.
+#[derive(Clone, Copy)]
+enum Exactness {
+    AsWritten,
+    Exact,
+    Inexact,
+}
+
+/// Reads the radix and exactness encoded by a (possibly empty) prefix
+/// matched by [`number_prefix`].
+fn read_prefix(prefix: &[u8]) -> (u32, Exactness) {
+    let mut radix = 10;
+    let mut exactness = Exactness::AsWritten;
+    for marker in prefix.chunks(2) {
+        match marker[1].to_ascii_lowercase() {
+            b'x' => radix = 16,
+            b'o' => radix = 8,
+            b'b' => radix = 2,
+            b'd' => radix = 10,
+            b'e' => exactness = Exactness::Exact,
+            b'i' => exactness = Exactness::Inexact,
+            _ => unreachable!("number_prefix only matches radix/exactness markers"),
+        }
+    }
+    (radix, exactness)
+}
+
+/// Parses an unsigned digit run in `radix` into an `i64`.
+fn parse_digits(digits: &[u8], radix: u32, offset: usize) -> Result<i64, ParseError> {
+    let overflow = || ParseError { kind: ParseErrorKind::NumberOverflow, offset };
+    let mut value = 0i64;
+    for &c in digits {
+        let d = i64::from(
+            (c as char)
+                .to_digit(radix)
+                .expect("recognizer only matches valid digits for this radix"),
+        );
+        value = value.checked_mul(i64::from(radix)).and_then(|v| v.checked_add(d)).ok_or_else(overflow)?;
+    }
+    Ok(value)
+}
+
+/// Returns a signed numeric literal's magnitude as an `f64`.
+///
+/// Converting an `i64` to `f64` is inherently lossy for large magnitudes;
+/// that is exactly what `#i`/inexact conversion asks for, so the loss is
+/// intentional rather than a bug to fix.
+#[allow(clippy::cast_precision_loss)]
+fn to_f64(value: &Handle) -> f64 {
+    match *value.borrow() {
+        Object::Int64(n) => n as f64,
+        Object::Rational(n, d) => (n as f64) / (d as f64),
+        _ => unreachable!("only produced from Int64 or Rational"),
+    }
+}
+
+/// Converts a decimal literal's text (no radix/exactness prefix, no sign) to
+/// an exact rational, negated if `negative`, honoring an explicit `#e` prefix.
+fn decimal_to_exact(text: &str, negative: bool, offset: usize) -> Result<Handle, ParseError> {
+    let overflow = || ParseError { kind: ParseErrorKind::NumberOverflow, offset };
+    let (mantissa, exp) = match text.split_once(['e', 'E']) {
+        Some((m, e)) => (m, e.parse::<i32>().map_err(|_| overflow())?),
+        None => (text, 0),
+    };
+    let (int_part, frac_part) = mantissa.split_once('.').unwrap_or((mantissa, ""));
+    let digits: Vec<u8> = int_part.bytes().chain(frac_part.bytes()).collect();
+    let numerator = parse_digits(&digits, 10, offset)?;
+    let numerator = if negative { -numerator } else { numerator };
+    let scale = i32::try_from(frac_part.len()).map_err(|_| overflow())? - exp;
+    if scale >= 0 {
+        let denominator =
+            10i64.checked_pow(u32::try_from(scale).map_err(|_| overflow())?).ok_or_else(overflow)?;
+        Ok(Handle::new_rational(numerator, denominator))
+    } else {
+        let scale_up =
+            10i64.checked_pow(u32::try_from(-scale).map_err(|_| overflow())?).ok_or_else(overflow)?;
+        let numerator = numerator.checked_mul(scale_up).ok_or_else(overflow)?;
+        Ok(Handle::new_rational(numerator, 1))
+    }
+}
+
+/// Builds the numeric object denoted by `body` (the number, sign included,
+/// with any `#`-prefix already stripped), applying `radix` and `exactness`.
+fn classify_number(
+    body: &[u8],
+    radix: u32,
+    exactness: Exactness,
+    offset: usize,
+) -> Result<Handle, ParseError> {
+    let negative = body.first() == Some(&b'-');
+    let unsigned = if matches!(body.first(), Some(b'+' | b'-')) { &body[1..] } else { body };
+    let overflow = || ParseError { kind: ParseErrorKind::NumberOverflow, offset };
+    let negate = |n: i64| if negative { n.checked_neg() } else { Some(n) };
+
+    if let Some(slash) = unsigned.iter().position(|&b| b == b'/') {
+        let numerator = negate(parse_digits(&unsigned[..slash], radix, offset)?).ok_or_else(overflow)?;
+        let denominator = parse_digits(&unsigned[slash + 1..], radix, offset)?;
+        if denominator == 0 {
+            return Err(ParseError { kind: ParseErrorKind::DivisionByZero, offset });
+        }
+        let value = Handle::new_rational(numerator, denominator);
+        return Ok(match exactness {
+            Exactness::Inexact => Handle::new_float64(to_f64(&value)),
+            Exactness::AsWritten | Exactness::Exact => value,
+        });
+    }
+
+    if radix == 10 && unsigned.iter().any(|&b| matches!(b, b'.' | b'e' | b'E')) {
+        let text = std::str::from_utf8(body).expect("recognizer only matches ASCII");
+        return match exactness {
+            Exactness::Exact => {
+                let unsigned_text = std::str::from_utf8(unsigned).expect("recognizer only matches ASCII");
+                decimal_to_exact(unsigned_text, negative, offset)
+            }
+            Exactness::AsWritten | Exactness::Inexact => {
+                Ok(Handle::new_float64(text.parse().map_err(|_| overflow())?))
+            }
+        };
+    }
+
+    let magnitude = negate(parse_digits(unsigned, radix, offset)?).ok_or_else(overflow)?;
+    Ok(match exactness {
+        // Lossy for large magnitudes, as intended: this is the `#i` prefix
+        // asking for an inexact (float) reading of an exact literal.
+        #[allow(clippy::cast_precision_loss)]
+        Exactness::Inexact => Handle::new_float64(magnitude as f64),
+        Exactness::AsWritten | Exactness::Exact => Handle::new_int64(magnitude),
+    })
+}
+
+/// Tries to read `atom` as a number. Returns `Ok(None)` (not an error) when
+/// `atom` does not denote a number, so the caller can fall back to treating
+/// it as a symbol.
+fn parse_number(atom: &[u8], offset: usize) -> Result<Option<Handle>, ParseError> {
+    let prefix_len = number_prefix().recognize(atom).unwrap_or(0);
+    let (prefix, body) = atom.split_at(prefix_len);
+    let (radix, exactness) = read_prefix(prefix);
+    if body.is_empty() || number(radix).recognize(body) != Some(body.len()) {
+        return Ok(None);
+    }
+    classify_number(body, radix, exactness, offset).map(Some)
+}
+
+/// Classifies a hash-prefixed atom as `#t`/`#true` or `#f`/`#false`.
+fn parse_boolean(atom: &[u8]) -> Option<bool> {
+    match atom {
+        b"#t" | b"#true" => Some(true),
+        b"#f" | b"#false" => Some(false),
+        _ => None,
+    }
+}
+
+fn make_atom(vec: Vec<u8>, offset: usize) -> Result<Handle, ParseError> {
+    if let Some(b) = parse_boolean(&vec) {
+        return Ok(Handle::new_bool(b));
+    }
+    match parse_number(&vec, offset)? {
+        Some(handle) => Ok(handle),
+        None => make_symbol(vec, offset),
+    }
+}
+
+fn make_string(vec: Vec<u8>, offset: usize) -> Result<Handle, ParseError> {
+    let s = String::from_utf8(vec)
+        .map_err(|_| ParseError { kind: ParseErrorKind::InvalidUtf8, offset })?;
+    Ok(Handle::new_string(s))
+}
+
+/// Decodes the UTF-8 scalar value beginning with `first`, a byte already
+/// consumed from `input`, reading whatever continuation bytes its encoding
+/// requires.
+///
+/// # Errors
+///
+/// Returns [`ParseErrorKind::InvalidUtf8`] if `first` is not a valid UTF-8
+/// lead byte or the bytes that follow do not complete a valid scalar value,
+/// or [`ParseErrorKind::UnexpectedEof`] if the stream ends first.
+fn read_utf8_char(input: &mut Input<impl Read>, first: u8) -> Result<char, ParseError> {
+    let len = match first {
+        0x00..=0x7f => 1,
+        0xc2..=0xdf => 2,
+        0xe0..=0xef => 3,
+        0xf0..=0xf4 => 4,
+        _ => return Err(err(input, ParseErrorKind::InvalidUtf8)),
+    };
+    let mut bytes = [0; 4];
+    bytes[0] = first;
+    for slot in &mut bytes[1..len] {
+        *slot = get(input)?.ok_or_else(|| err(input, ParseErrorKind::UnexpectedEof))?;
+    }
+    std::str::from_utf8(&bytes[..len])
+        .ok()
+        .and_then(|s| s.chars().next())
+        .ok_or_else(|| err(input, ParseErrorKind::InvalidUtf8))
+}
+
+/// Builds the character denoted by a `#\` token's body: a single ASCII byte
+/// for a literal character too short to be a name (a bare non-ASCII literal
+/// is handled by [`read_utf8_char`] before it ever reaches here), or the
+/// name of one of R7RS's named characters, or an `xHHHH` hex scalar value.
+fn make_char(name: &[u8], offset: usize) -> Result<Handle, ParseError> {
+    if let [byte] = *name {
+        return Ok(Handle::new_char(char::from(byte)));
+    }
+    let invalid = || ParseError { kind: ParseErrorKind::InvalidCharacterName, offset };
+    let name = std::str::from_utf8(name).map_err(|_| invalid())?;
+    let c = match name.to_ascii_lowercase().as_str() {
+        "space" => ' ',
+        "newline" | "linefeed" => '\n',
+        "tab" => '\t',
+        "return" => '\r',
+        "null" | "nul" => '\0',
+        "alarm" => '\u{7}',
+        "backspace" => '\u{8}',
+        "escape" | "altmode" => '\u{1b}',
+        "delete" | "rubout" => '\u{7f}',
+        _ => {
+            let code = name.strip_prefix('x').and_then(|hex| u32::from_str_radix(hex, 16).ok());
+            return code.and_then(char::from_u32).map(Handle::new_char).ok_or_else(invalid);
+        }
+    };
+    Ok(Handle::new_char(c))
+}
+
+/// Wraps `datum` in `(name datum)`, the expansion of a reader shorthand like
+/// `'datum` or `` `datum ``.
+fn quote_wrap(name: &str, datum: Handle) -> Handle {
+    make_list(vec![Handle::new_symbol(name.to_string()), datum, Handle::new_nil()])
+}
+
+/// Reads the character after a `,`, distinguishing `,@` (unquote-splicing)
+/// from a plain `,` (unquote).
+fn read_unquote(input: &mut Input<impl Read>) -> Result<Handle, ParseError> {
+    match get(input)? {
+        Some(b'@') => Ok(quote_wrap("unquote-splicing", read(input)?)),
+        Some(c) => {
+            input.push(c);
+            Ok(quote_wrap("unquote", read(input)?))
+        }
+        None => Err(err(input, ParseErrorKind::UnexpectedEof)),
+    }
+}
+
+/// Skips a `#| ... |#` block comment, which may nest, leaving `input`
+/// positioned just after the closing `|#`.
+fn skip_block_comment(input: &mut Input<impl Read>) -> Result<(), ParseError> {
+    let mut depth = 1u32;
+    while depth > 0 {
+        match get(input)? {
+            Some(b'#') => match get(input)? {
+                Some(b'|') => depth += 1,
+                Some(c) => input.push(c),
+                None => return Err(err(input, ParseErrorKind::UnexpectedEof)),
+            },
+            Some(b'|') => match get(input)? {
+                Some(b'#') => depth -= 1,
+                Some(c) => input.push(c),
+                None => return Err(err(input, ParseErrorKind::UnexpectedEof)),
+            },
+            Some(_) => (),
+            None => return Err(err(input, ParseErrorKind::UnexpectedEof)),
+        }
+    }
+    Ok(())
+}
+
+/// Consumes whitespace, `;` line comments, `#| ... |#` block comments, and
+/// `#;` datum comments, returning the first byte that begins real content (or
+/// `None` at end of input). That byte is left consumed from `input`, exactly
+/// as [`get`] would leave it.
+fn skip_trivia(input: &mut Input<impl Read>) -> Result<Option<u8>, ParseError> {
+    loop {
+        match get(input)? {
+            Some(b' ' | b'\t' | b'\n') => (),
+            Some(b';') => while !matches!(get(input)?, Some(b'\n') | None) {},
+            Some(b'#') => match get(input)? {
+                Some(b'|') => skip_block_comment(input)?,
+                Some(b';') => {
+                    read(input)?;
+                }
+                Some(c) => {
+                    input.push(c);
+                    input.push(b'#');
+                    return get(input);
+                }
+                None => return Err(err(input, ParseErrorKind::UnexpectedEof)),
+            },
+            other => return Ok(other),
+        }
+    }
+}
+
 fn is_symbol_char(byte: u8) -> bool {
     matches!(byte,
-             b'a'..=b'z' | b'A'..=b'Z' | b'0'..=b'9' | b'!' | b'$' | b'%' | b'&' | b'*' | b'+' |
-             b'-' | b'.' | b'/' | b':' | b'<' | b'=' | b'>' | b'?' | b'@' | b'^' | b'_' | b'~')
+             b'a'..=b'z' | b'A'..=b'Z' | b'0'..=b'9' | b'!' | b'#' | b'$' | b'%' | b'&' | b'*' |
+             b'+' | b'-' | b'.' | b'/' | b':' | b'<' | b'=' | b'>' | b'?' | b'@' | b'^' | b'_' |
+             b'~')
 }
 
-pub fn read(input: &mut Input<impl Read>) -> Handle {
+/// Reads a single datum from `input`.
+///
+/// Returns `Object::Eof` once `input` is exhausted with no partial datum
+/// pending. On malformed input, returns a [`ParseError`] describing what went
+/// wrong and where; `input` is left positioned after the offending byte, so
+/// callers may recover and keep reading (e.g. on the next REPL line).
+///
+/// # Errors
+///
+/// Returns a [`ParseError`] if `input` does not contain a well-formed datum.
+#[allow(clippy::too_many_lines)]
+pub fn read(input: &mut Input<impl Read>) -> Result<Handle, ParseError> {
     let mut state = ParseState::None;
     loop {
-        let c = input.get();
         state = match state {
-            ParseState::None => match c {
-                Some(b' ' | b'\t' | b'\n') => ParseState::None,
+            ParseState::None => match skip_trivia(input)? {
                 Some(b'(') => ParseState::List(Vec::new()),
                 Some(b'"') => ParseState::String(Vec::new()),
-                Some(b'\'') => {
-                    return make_list(vec![
-                        Handle::new_symbol("quote".to_string()),
-                        read(input),
-                        Handle::new_nil(),
-                    ]);
-                }
-                Some(b')') => panic!("Error parsing: unexpected `)`."),
-                Some(c @ (b'0'..=b'9' | b'-' | b'+')) => ParseState::Int(vec![c]),
-                Some(c) if is_symbol_char(c) => ParseState::Symbol(vec![c]),
-                Some(c) => panic!("Error parsing: unexpected `{}`.", c.escape_ascii()),
-                None => return Handle::new_eof(),
-            },
-            ParseState::List(mut v) => match c {
-                Some(b'\n' | b' ') => ParseState::List(v),
+                Some(b'\'') => return Ok(quote_wrap("quote", read(input)?)),
+                Some(b'`') => return Ok(quote_wrap("quasiquote", read(input)?)),
+                Some(b',') => return read_unquote(input),
+                Some(b')') => return Err(err(input, ParseErrorKind::UnexpectedCloseParen)),
+                Some(b'#') => ParseState::Hash,
+                Some(c) if is_symbol_char(c) => ParseState::Atom(vec![c]),
+                Some(c) => return Err(err(input, ParseErrorKind::UnexpectedChar(c))),
+                None => return Ok(Handle::new_eof()),
+            },
+            ParseState::List(mut v) => match skip_trivia(input)? {
                 Some(b')') => {
                     v.push(Handle::new_nil());
-                    return make_list(v);
+                    return Ok(make_list(v));
                 }
                 Some(b'.') => ParseState::MaybeDot(v),
                 Some(c) => {
                     input.push(c);
-                    v.push(read(input));
+                    v.push(read(input)?);
                     ParseState::List(v)
                 }
-                None => panic!("Error parsing list: unexpected EOF."),
+                None => return Err(err(input, ParseErrorKind::UnexpectedEof)),
             },
-            ParseState::MaybeDot(mut v) => match c {
+            ParseState::MaybeDot(mut v) => match get(input)? {
                 Some(b' ' | b'\t' | b'\n') => {
-                    assert!(!v.is_empty(), "Error parsing list: unexpected `.`");
-                    v.push(read(input));
+                    if v.is_empty() {
+                        return Err(err(input, ParseErrorKind::MisplacedDot));
+                    }
+                    v.push(read(input)?);
                     ParseState::ListEnd(v)
                 }
                 Some(c) => {
                     input.push(c);
                     input.push(b'.');
-                    v.push(read(input));
+                    v.push(read(input)?);
                     ParseState::List(v)
                 }
-                None => panic!("Error parsing list: unexpected EOF."),
+                None => return Err(err(input, ParseErrorKind::UnexpectedEof)),
             },
-            ParseState::ListEnd(v) => match c {
-                Some(b' ' | b'\t' | b'\n') => ParseState::ListEnd(v),
-                Some(b')') => return make_list(v),
-                Some(_) => panic!("Error parsing list: expected `)`."),
-                None => panic!("Error parsing list: unexpected EOF."),
+            ParseState::ListEnd(v) => match skip_trivia(input)? {
+                Some(b')') => return Ok(make_list(v)),
+                Some(c) => return Err(err(input, ParseErrorKind::UnexpectedChar(c))),
+                None => return Err(err(input, ParseErrorKind::UnexpectedEof)),
             },
-            ParseState::Int(mut v) => match c {
-                Some(c @ (b' ' | b'\t' | b'\n' | b'(' | b')')) => {
+            ParseState::Atom(mut v) => match get(input)? {
+                Some(c @ (b' ' | b'\t' | b'\n' | b'(' | b')' | b'"' | b';')) => {
                     input.push(c);
-                    return make_int(&v);
+                    return make_atom(v, input.pos());
                 }
-                Some(c @ b'0'..=b'9') => {
+                Some(c) if is_symbol_char(c) => {
                     v.push(c);
-                    ParseState::Int(v)
+                    ParseState::Atom(v)
                 }
-                Some(c) if is_symbol_char(c) => {
+                Some(c) => return Err(err(input, ParseErrorKind::UnexpectedChar(c))),
+                None => return make_atom(v, input.pos()),
+            },
+            ParseState::String(mut v) => match get(input)? {
+                Some(b'"') => return make_string(v, input.pos()),
+                Some(b'\\') => ParseState::StringEscape(v),
+                Some(c) => {
                     v.push(c);
-                    ParseState::Symbol(v)
+                    ParseState::String(v)
                 }
-                Some(c) => panic!("Error parsing: unexpected `{}`.", c.escape_ascii()),
-                None => return make_int(&v),
+                None => return Err(err(input, ParseErrorKind::UnexpectedEof)),
             },
-            ParseState::Symbol(mut v) => match c {
-                Some(c @ (b' ' | b'\t' | b'\n' | b'(' | b')')) => {
-                    input.push(c);
-                    return make_symbol(v);
+            ParseState::StringEscape(mut v) => match get(input)? {
+                Some(b'n') => {
+                    v.push(b'\n');
+                    ParseState::String(v)
                 }
-                Some(c) if is_symbol_char(c) => {
+                Some(b't') => {
+                    v.push(b'\t');
+                    ParseState::String(v)
+                }
+                Some(b'r') => {
+                    v.push(b'\r');
+                    ParseState::String(v)
+                }
+                Some(b'a') => {
+                    v.push(7);
+                    ParseState::String(v)
+                }
+                Some(b'b') => {
+                    v.push(8);
+                    ParseState::String(v)
+                }
+                Some(c @ (b'\\' | b'"')) => {
                     v.push(c);
-                    ParseState::Symbol(v)
+                    ParseState::String(v)
                 }
-                Some(c) => panic!("Error parsing: unexpected `{}`.", c.escape_ascii()),
-                None => return make_symbol(v),
+                Some(b'x') => ParseState::StringHexEscape(v, Vec::new()),
+                Some(b'\n') => ParseState::StringLineContinuation(v),
+                Some(c) => return Err(err(input, ParseErrorKind::UnexpectedChar(c))),
+                None => return Err(err(input, ParseErrorKind::UnexpectedEof)),
             },
-            ParseState::String(mut v) => match c {
-                Some(b'"') => return make_string(v),
-                Some(b'\\') => ParseState::String(v),
+            ParseState::StringHexEscape(mut v, mut digits) => match get(input)? {
+                Some(b';') => {
+                    let offset = input.pos();
+                    let invalid = || ParseError { kind: ParseErrorKind::InvalidCharacterName, offset };
+                    let hex = std::str::from_utf8(&digits).map_err(|_| invalid())?;
+                    let code = u32::from_str_radix(hex, 16).map_err(|_| invalid())?;
+                    let c = char::from_u32(code).ok_or_else(invalid)?;
+                    let mut buf = [0u8; 4];
+                    v.extend_from_slice(c.encode_utf8(&mut buf).as_bytes());
+                    ParseState::String(v)
+                }
+                Some(c) if c.is_ascii_hexdigit() => {
+                    digits.push(c);
+                    ParseState::StringHexEscape(v, digits)
+                }
+                Some(c) => return Err(err(input, ParseErrorKind::UnexpectedChar(c))),
+                None => return Err(err(input, ParseErrorKind::UnexpectedEof)),
+            },
+            ParseState::StringLineContinuation(v) => match get(input)? {
+                Some(b' ' | b'\t') => ParseState::StringLineContinuation(v),
                 Some(c) => {
-                    v.push(c);
+                    input.push(c);
                     ParseState::String(v)
                 }
-                None => panic!("Error parsing string: unexpected EOF."),
+                None => return Err(err(input, ParseErrorKind::UnexpectedEof)),
+            },
+            ParseState::Hash => match get(input)? {
+                Some(b'(') => ParseState::Vector(Vec::new()),
+                Some(b'\\') => ParseState::CharStart,
+                Some(c) if is_symbol_char(c) => ParseState::Atom(vec![b'#', c]),
+                Some(c) => return Err(err(input, ParseErrorKind::UnexpectedChar(c))),
+                None => return Err(err(input, ParseErrorKind::UnexpectedEof)),
+            },
+            ParseState::Vector(mut v) => match skip_trivia(input)? {
+                Some(b')') => return Ok(Handle::new_vector(v)),
+                Some(c) => {
+                    input.push(c);
+                    v.push(read(input)?);
+                    ParseState::Vector(v)
+                }
+                None => return Err(err(input, ParseErrorKind::UnexpectedEof)),
+            },
+            ParseState::CharStart => match get(input)? {
+                Some(c) if c.is_ascii_alphabetic() => ParseState::Char(vec![c]),
+                Some(c) => return Ok(Handle::new_char(read_utf8_char(input, c)?)),
+                None => return Err(err(input, ParseErrorKind::UnexpectedEof)),
+            },
+            ParseState::Char(mut v) => match get(input)? {
+                Some(c) if is_symbol_char(c) => {
+                    v.push(c);
+                    ParseState::Char(v)
+                }
+                Some(c) => {
+                    input.push(c);
+                    return make_char(&v, input.pos());
+                }
+                None => return make_char(&v, input.pos()),
             },
         };
     }
@@ -190,7 +754,11 @@ mod tests {
     use super::*;
 
     fn read_str(input: &str) -> Handle {
-        read(&mut Input::new(&mut Cursor::new(input)))
+        read(&mut Input::new(&mut Cursor::new(input))).unwrap()
+    }
+
+    fn read_str_err(input: &str) -> ParseError {
+        read(&mut Input::new(&mut Cursor::new(input))).unwrap_err()
     }
 
     #[test]
@@ -229,4 +797,166 @@ mod tests {
             )
         );
     }
+
+    #[test]
+    fn unexpected_close_paren() {
+        assert_eq!(read_str_err(")").kind, ParseErrorKind::UnexpectedCloseParen);
+    }
+
+    #[test]
+    fn unexpected_eof_in_list() {
+        assert_eq!(read_str_err("(1 2").kind, ParseErrorKind::UnexpectedEof);
+    }
+
+    #[test]
+    fn unexpected_eof_in_string() {
+        assert_eq!(read_str_err("\"abc").kind, ParseErrorKind::UnexpectedEof);
+    }
+
+    #[test]
+    fn misplaced_dot() {
+        assert_eq!(read_str_err("(. 1)").kind, ParseErrorKind::MisplacedDot);
+    }
+
+    #[test]
+    fn number_overflow() {
+        assert_eq!(
+            read_str_err("99999999999999999999").kind,
+            ParseErrorKind::NumberOverflow
+        );
+    }
+
+    #[test]
+    fn bare_sign_is_a_symbol() {
+        assert_eq!(read_str("-"), Handle::new_symbol("-".to_string()));
+        assert_eq!(read_str("+"), Handle::new_symbol("+".to_string()));
+        assert_eq!(read_str("-x"), Handle::new_symbol("-x".to_string()));
+    }
+
+    #[test]
+    fn hex_digits_e_and_dot_are_not_mistaken_for_a_decimal_float() {
+        assert_eq!(read_str("#xE"), Handle::new_int64(14));
+        assert_eq!(read_str("#xEA"), Handle::new_int64(234));
+        assert_eq!(read_str("#xDECADE"), Handle::new_int64(0xDECADE));
+        assert_eq!(read_str("#x1.8"), Handle::new_symbol("#x1.8".to_string()));
+    }
+
+    #[test]
+    fn radix_prefixes() {
+        assert_eq!(read_str("#x1A"), Handle::new_int64(26));
+        assert_eq!(read_str("#o17"), Handle::new_int64(15));
+        assert_eq!(read_str("#b101"), Handle::new_int64(5));
+        assert_eq!(read_str("#d42"), Handle::new_int64(42));
+        assert_eq!(read_str("#x-1A"), Handle::new_int64(-26));
+    }
+
+    #[test]
+    fn rationals() {
+        assert_eq!(read_str("3/4"), Handle::new_rational(3, 4));
+        assert_eq!(read_str("-3/4"), Handle::new_rational(-3, 4));
+        assert_eq!(read_str("6/3"), Handle::new_int64(2));
+        assert_eq!(read_str("#x1/2"), Handle::new_rational(1, 2));
+    }
+
+    #[test]
+    fn division_by_zero() {
+        assert_eq!(read_str_err("1/0").kind, ParseErrorKind::DivisionByZero);
+    }
+
+    #[test]
+    fn decimals() {
+        assert_eq!(read_str("1.5"), Handle::new_float64(1.5));
+        assert_eq!(read_str(".5"), Handle::new_float64(0.5));
+        assert_eq!(read_str("1."), Handle::new_float64(1.0));
+        assert_eq!(read_str("1e3"), Handle::new_float64(1000.0));
+        assert_eq!(read_str("1.5e2"), Handle::new_float64(150.0));
+    }
+
+    #[test]
+    fn exactness_prefixes() {
+        assert_eq!(read_str("#i1"), Handle::new_float64(1.0));
+        assert_eq!(read_str("#i1/2"), Handle::new_float64(0.5));
+        assert_eq!(read_str("#e1.5"), Handle::new_rational(3, 2));
+        assert_eq!(read_str("#e#x10"), Handle::new_int64(16));
+    }
+
+    #[test]
+    fn unknown_hash_atom_falls_back_to_symbol() {
+        assert_eq!(read_str("#xyz"), Handle::new_symbol("#xyz".to_string()));
+    }
+
+    #[test]
+    fn booleans() {
+        assert_eq!(read_str("#t"), Handle::new_bool(true));
+        assert_eq!(read_str("#true"), Handle::new_bool(true));
+        assert_eq!(read_str("#f"), Handle::new_bool(false));
+        assert_eq!(read_str("#false"), Handle::new_bool(false));
+    }
+
+    #[test]
+    fn characters() {
+        assert_eq!(read_str("#\\a"), Handle::new_char('a'));
+        assert_eq!(read_str("#\\("), Handle::new_char('('));
+        assert_eq!(read_str("#\\space"), Handle::new_char(' '));
+        assert_eq!(read_str("#\\newline"), Handle::new_char('\n'));
+        assert_eq!(read_str("#\\x41"), Handle::new_char('A'));
+    }
+
+    #[test]
+    fn non_ascii_character() {
+        assert_eq!(read_str("#\\é"), Handle::new_char('é'));
+        assert_eq!(read_str("#\\🍞"), Handle::new_char('🍞'));
+    }
+
+    #[test]
+    fn vectors() {
+        assert_eq!(read_str("#()"), Handle::new_vector(Vec::new()));
+        assert_eq!(
+            read_str("#(1 2)"),
+            Handle::new_vector(vec![Handle::new_int64(1), Handle::new_int64(2)])
+        );
+    }
+
+    #[test]
+    fn quote_family() {
+        let wrap = |name: &str, datum: Handle| {
+            Handle::new_cons(
+                Handle::new_symbol(name.to_string()),
+                Handle::new_cons(datum, Handle::new_nil()),
+            )
+        };
+        assert_eq!(read_str("'a"), wrap("quote", Handle::new_symbol("a".to_string())));
+        assert_eq!(read_str("`a"), wrap("quasiquote", Handle::new_symbol("a".to_string())));
+        assert_eq!(read_str(",a"), wrap("unquote", Handle::new_symbol("a".to_string())));
+        assert_eq!(
+            read_str(",@a"),
+            wrap("unquote-splicing", Handle::new_symbol("a".to_string()))
+        );
+    }
+
+    #[test]
+    fn line_comments() {
+        assert_eq!(read_str("1 ; comment\n"), Handle::new_int64(1));
+        assert_eq!(read_str("; comment\n1"), Handle::new_int64(1));
+    }
+
+    #[test]
+    fn block_comments() {
+        assert_eq!(read_str("#| comment |#1"), Handle::new_int64(1));
+        assert_eq!(read_str("#| outer #| inner |# still outer |#1"), Handle::new_int64(1));
+    }
+
+    #[test]
+    fn datum_comments() {
+        assert_eq!(read_str("(1 #;2 3)"), read_str("(1 3)"));
+    }
+
+    #[test]
+    fn string_escapes() {
+        assert_eq!(read_str(r#""a\nb""#), Handle::new_string("a\nb".to_string()));
+        assert_eq!(read_str(r#""a\tb""#), Handle::new_string("a\tb".to_string()));
+        assert_eq!(read_str(r#""a\"b""#), Handle::new_string("a\"b".to_string()));
+        assert_eq!(read_str(r#""a\x41;b""#), Handle::new_string("aAb".to_string()));
+        assert_eq!(read_str("\"a\\\n   b\""), Handle::new_string("ab".to_string()));
+    }
 }
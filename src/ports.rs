@@ -0,0 +1,286 @@
+// bread-scheme -- R7RS Scheme interpreter
+// Copyright (C) 2023 Archit Gupta <archit@accelbread.com>
+//
+// This program is free software: you can redistribute it and/or modify it under
+// the terms of the GNU Affero General Public License as published by the Free
+// Software Foundation, either version 3 of the License, or (at your option) any
+// later version.
+//
+// This program is distributed in the hope that it will be useful, but WITHOUT
+// ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or FITNESS
+// FOR A PARTICULAR PURPOSE. See the GNU Affero General Public License for more
+// details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+//
+// SPDX-License-Identifier: AGPL-3.0-or-later
+
+//! The operations behind Scheme's port primitives (`open-input-file`,
+//! `open-output-file`, `open-input-string`, `read`, `write`, `read-char`,
+//! `peek-char`, `load`).
+//!
+//! These are plain Rust functions, not entries in a primitive table: `eval`
+//! has no notion of a global environment or builtin dispatch to register
+//! against in this tree, so wiring each of these under its Scheme name is a
+//! deliberate follow-up, not an oversight here. Inventing that dispatch
+//! mechanism as a side effect of this change would be guesswork about a
+//! shape this codebase hasn't settled on yet.
+
+#![allow(dead_code)]
+
+use crate::{
+    eval::eval,
+    input::Input,
+    parser::{read, ParseError},
+    types::{Handle, Object},
+};
+use std::{
+    cell::RefCell,
+    fs::File,
+    io::{self, Cursor, Read, Write},
+    rc::Rc,
+};
+
+/// Opens `path` for reading, returning a [`Handle`] wrapping an input port.
+///
+/// # Errors
+///
+/// Returns an error if `path` cannot be opened.
+pub fn open_input_file(path: &str) -> io::Result<Handle> {
+    let stream: Box<dyn Read> = Box::new(File::open(path)?);
+    Ok(Handle::new_input_port(Rc::new(RefCell::new(Input::new(stream)))))
+}
+
+/// Opens `path` for writing, creating it if absent and truncating it
+/// otherwise, returning a [`Handle`] wrapping an output port.
+///
+/// # Errors
+///
+/// Returns an error if `path` cannot be created.
+pub fn open_output_file(path: &str) -> io::Result<Handle> {
+    let stream: Box<dyn Write> = Box::new(File::create(path)?);
+    Ok(Handle::new_output_port(Rc::new(RefCell::new(stream))))
+}
+
+/// Opens `contents` as an input port reading from an in-memory string,
+/// rather than a file.
+#[must_use]
+pub fn open_input_string(contents: String) -> Handle {
+    let stream: Box<dyn Read> = Box::new(Cursor::new(contents.into_bytes()));
+    Handle::new_input_port(Rc::new(RefCell::new(Input::new(stream))))
+}
+
+/// Reads a single datum from `port`.
+///
+/// # Errors
+///
+/// Returns a [`PortError`] if `port` is not an input port, or does not
+/// contain a well-formed datum.
+pub fn read_port(port: &Handle) -> Result<Handle, PortError> {
+    with_input(port, |input| read(input).map_err(PortError::Parse))
+}
+
+/// Reads a single character from `port`, or `Object::Eof` at end of stream.
+///
+/// # Errors
+///
+/// Returns an error if `port` is not an input port, the underlying stream
+/// fails to read, or the bytes read do not form a valid UTF-8 scalar value.
+pub fn read_char(port: &Handle) -> Result<Handle, PortError> {
+    with_input(port, |input| match input.get().map_err(PortError::Io)? {
+        Some(byte) => Ok(Handle::new_char(read_utf8_char(input, byte).map_err(PortError::Io)?)),
+        None => Ok(Handle::new_eof()),
+    })
+}
+
+/// Like [`read_char`], but leaves the character in `port` to be read again.
+///
+/// # Errors
+///
+/// Returns an error if `port` is not an input port, the underlying stream
+/// fails to read, or the bytes read do not form a valid UTF-8 scalar value.
+pub fn peek_char(port: &Handle) -> Result<Handle, PortError> {
+    with_input(port, |input| match input.get().map_err(PortError::Io)? {
+        Some(byte) => {
+            let c = read_utf8_char(input, byte).map_err(PortError::Io)?;
+            let mut encoded = [0; 4];
+            for &b in c.encode_utf8(&mut encoded).as_bytes().iter().rev() {
+                input.push(b);
+            }
+            Ok(Handle::new_char(c))
+        }
+        None => Ok(Handle::new_eof()),
+    })
+}
+
+/// Decodes the UTF-8 scalar value beginning with `first`, a byte already
+/// consumed from `input`, reading whatever continuation bytes its encoding
+/// requires.
+///
+/// # Errors
+///
+/// Returns an error if the underlying stream fails to read, or if `first`
+/// is not a valid UTF-8 lead byte, or the bytes that follow do not complete
+/// a valid scalar value.
+fn read_utf8_char(input: &mut Input<Box<dyn Read>>, first: u8) -> io::Result<char> {
+    let invalid = || io::Error::new(io::ErrorKind::InvalidData, "invalid UTF-8 in port");
+    let len = match first {
+        0x00..=0x7f => 1,
+        0xc2..=0xdf => 2,
+        0xe0..=0xef => 3,
+        0xf0..=0xf4 => 4,
+        _ => return Err(invalid()),
+    };
+    let mut bytes = [0; 4];
+    bytes[0] = first;
+    for slot in &mut bytes[1..len] {
+        *slot = input.get()?.ok_or_else(invalid)?;
+    }
+    std::str::from_utf8(&bytes[..len]).ok().and_then(|s| s.chars().next()).ok_or_else(invalid)
+}
+
+/// Writes `value` to `port` in `write` representation.
+///
+/// # Errors
+///
+/// Returns an error if `port` is not an output port, or the underlying
+/// stream fails to write.
+pub fn write_port(value: &Handle, port: &Handle) -> Result<(), PortError> {
+    let stream = match &*port.borrow() {
+        Object::Port(crate::types::Port::Output(stream)) => stream.clone(),
+        _ => return Err(PortError::NotAnOutputPort),
+    };
+    let result = write!(stream.borrow_mut(), "{value}").map_err(PortError::Io);
+    result
+}
+
+/// Reads and evaluates every datum in the file at `path`, in order. Results
+/// are discarded, not printed.
+///
+/// # Errors
+///
+/// Returns an error if `path` cannot be opened or contains malformed input;
+/// reading stops at the first malformed datum.
+pub fn load(path: &str) -> Result<(), PortError> {
+    let port = open_input_file(path).map_err(PortError::Io)?;
+    loop {
+        let datum = read_port(&port)?;
+        if let Object::Eof = *datum.borrow() {
+            return Ok(());
+        }
+        eval(datum);
+    }
+}
+
+/// Runs `f` against the [`Input`] behind `port`.
+fn with_input<T>(
+    port: &Handle,
+    f: impl FnOnce(&mut Input<Box<dyn Read>>) -> Result<T, PortError>,
+) -> Result<T, PortError> {
+    let input = match &*port.borrow() {
+        Object::Port(crate::types::Port::Input(input)) => input.clone(),
+        _ => return Err(PortError::NotAnInputPort),
+    };
+    let result = f(&mut input.borrow_mut());
+    result
+}
+
+/// An error produced while operating on a port.
+#[derive(Debug)]
+pub enum PortError {
+    /// The operation expected an input port but was given something else.
+    NotAnInputPort,
+    /// The operation expected an output port but was given something else.
+    NotAnOutputPort,
+    /// The port did not contain a well-formed datum.
+    Parse(ParseError),
+    /// The underlying stream could not be opened, read or written, or the
+    /// bytes read from it were not valid UTF-8.
+    Io(io::Error),
+}
+
+impl std::fmt::Display for PortError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::NotAnInputPort => write!(f, "not an input port"),
+            Self::NotAnOutputPort => write!(f, "not an output port"),
+            Self::Parse(e) => write!(f, "{e}"),
+            Self::Io(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+impl std::error::Error for PortError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn read_char_and_peek_char() {
+        let port = open_input_string("ab".to_string());
+        assert_eq!(peek_char(&port).unwrap(), Handle::new_char('a'));
+        assert_eq!(read_char(&port).unwrap(), Handle::new_char('a'));
+        assert_eq!(read_char(&port).unwrap(), Handle::new_char('b'));
+        assert_eq!(read_char(&port).unwrap(), Handle::new_eof());
+    }
+
+    #[test]
+    fn read_char_decodes_multibyte_utf8() {
+        let port = open_input_string("é🍞".to_string());
+        assert_eq!(read_char(&port).unwrap(), Handle::new_char('é'));
+        assert_eq!(read_char(&port).unwrap(), Handle::new_char('🍞'));
+        assert_eq!(read_char(&port).unwrap(), Handle::new_eof());
+    }
+
+    #[test]
+    fn peek_char_leaves_a_multibyte_char_to_be_reread() {
+        let port = open_input_string("é".to_string());
+        assert_eq!(peek_char(&port).unwrap(), Handle::new_char('é'));
+        assert_eq!(read_char(&port).unwrap(), Handle::new_char('é'));
+        assert_eq!(read_char(&port).unwrap(), Handle::new_eof());
+    }
+
+    #[test]
+    fn read_port_reads_each_datum_in_turn() {
+        let port = open_input_string("1 2 3".to_string());
+        assert_eq!(read_port(&port).unwrap(), Handle::new_int64(1));
+        assert_eq!(read_port(&port).unwrap(), Handle::new_int64(2));
+        assert_eq!(read_port(&port).unwrap(), Handle::new_int64(3));
+        assert_eq!(read_port(&port).unwrap(), Handle::new_eof());
+    }
+
+    /// The pushback buffer backing `peek_char`/`read_char` lives on the
+    /// per-port `Input`, not anywhere process-global, so interleaving reads
+    /// from two ports must not let one port's lookahead bleed into the
+    /// other's.
+    #[test]
+    fn pushback_is_independent_per_port() {
+        let a = open_input_string("ab".to_string());
+        let b = open_input_string("xy".to_string());
+        assert_eq!(peek_char(&a).unwrap(), Handle::new_char('a'));
+        assert_eq!(peek_char(&b).unwrap(), Handle::new_char('x'));
+        assert_eq!(read_char(&b).unwrap(), Handle::new_char('x'));
+        assert_eq!(read_char(&a).unwrap(), Handle::new_char('a'));
+        assert_eq!(read_char(&a).unwrap(), Handle::new_char('b'));
+        assert_eq!(read_char(&b).unwrap(), Handle::new_char('y'));
+    }
+
+    #[test]
+    fn write_port_rejects_an_input_port() {
+        let port = open_input_string(String::new());
+        assert!(matches!(
+            write_port(&Handle::new_int64(1), &port),
+            Err(PortError::NotAnOutputPort)
+        ));
+    }
+
+    #[test]
+    fn read_port_rejects_an_output_port() {
+        let path = std::env::temp_dir().join(format!("bread-scheme-test-{}.scm", std::process::id()));
+        let port = open_output_file(path.to_str().unwrap()).unwrap();
+        assert!(matches!(read_port(&port), Err(PortError::NotAnInputPort)));
+        let _ = std::fs::remove_file(path);
+    }
+}
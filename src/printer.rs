@@ -16,19 +16,37 @@
 //
 // SPDX-License-Identifier: AGPL-3.0-or-later
 
-use crate::types::{Handle, Object};
+use crate::types::{format_char, format_float, Handle, Object};
 
 pub fn print(value: &Handle) {
     match *value.borrow() {
         Object::Cons(ref car, ref cdr) => print_cons(car, cdr),
-        Object::Nil => print!("()"),
+        Object::Empty => print!("()"),
         Object::Symbol(ref x) => print!("{x}"),
         Object::Int64(x) => print!("{x}"),
+        Object::Rational(n, d) => print!("{n}/{d}"),
+        Object::Float64(x) => print!("{}", format_float(x)),
         Object::String(ref x) => print!("\"{x}\""),
+        Object::Bool(true) => print!("#t"),
+        Object::Bool(false) => print!("#f"),
+        Object::Char(c) => print!("{}", format_char(c)),
+        Object::Vector(ref v) => print_vector(v),
+        Object::Port(_) => print!("#<port>"),
         Object::Eof => (),
     };
 }
 
+fn print_vector(elements: &[Handle]) {
+    print!("#(");
+    for (i, e) in elements.iter().enumerate() {
+        if i > 0 {
+            print!(" ");
+        }
+        print(e);
+    }
+    print!(")");
+}
+
 fn print_cons(car: &Handle, cdr: &Handle) {
     print!("(");
     print(car);
@@ -38,7 +56,7 @@ fn print_cons(car: &Handle, cdr: &Handle) {
         print(car);
         next = cdr.clone();
     }
-    if let Object::Nil = *next.borrow() {
+    if let Object::Empty = *next.borrow() {
     } else {
         print!(" . ");
         print(cdr);
@@ -16,23 +16,81 @@
 //
 // SPDX-License-Identifier: AGPL-3.0-or-later
 
+use crate::input::Input;
 use std::{
     cell::{Ref, RefCell},
     fmt::{self, Display},
+    io::{Read, Write},
     rc::Rc,
 };
 
-#[derive(Clone, Default, PartialEq, Eq)]
+/// An open Scheme I/O port.
+///
+/// Ports are reference-counted so the same open port can be shared across
+/// every `Handle` that refers to it (e.g. the result of `current-output-port`
+/// and a port passed explicitly to `write`); cloning a `Port` clones the
+/// handle, not the underlying stream.
+#[derive(Clone)]
+pub enum Port {
+    Input(Rc<RefCell<Input<Box<dyn Read>>>>),
+    Output(Rc<RefCell<Box<dyn Write>>>),
+}
+
+impl Port {
+    /// Ports compare equal when they refer to the same open stream, not when
+    /// their contents happen to match (there is no meaningful notion of the
+    /// latter for e.g. an output port).
+    fn is_same(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::Input(a), Self::Input(b)) => Rc::ptr_eq(a, b),
+            (Self::Output(a), Self::Output(b)) => Rc::ptr_eq(a, b),
+            (Self::Input(_), Self::Output(_)) | (Self::Output(_), Self::Input(_)) => false,
+        }
+    }
+}
+
+#[derive(Clone, Default)]
 pub enum Object {
     #[default]
     Empty,
     Cons(Handle, Handle),
     Symbol(String),
     Int64(i64),
+    /// An exact rational that is not an integer, stored as a reduced
+    /// numerator/denominator pair with a positive denominator.
+    Rational(i64, i64),
+    Float64(f64),
     String(String),
+    Bool(bool),
+    Char(char),
+    Vector(Vec<Handle>),
+    Port(Port),
     Eof,
 }
 
+// Equality here is structural identity (as used by `assert_eq!` and
+// friends), not Scheme's numeric `=`; floats compare bit-for-bit and ports
+// compare by identity, so `Object` can be Eq.
+impl PartialEq for Object {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::Empty, Self::Empty) | (Self::Eof, Self::Eof) => true,
+            (Self::Cons(a1, a2), Self::Cons(b1, b2)) => a1 == b1 && a2 == b2,
+            (Self::Symbol(a), Self::Symbol(b)) | (Self::String(a), Self::String(b)) => a == b,
+            (Self::Int64(a), Self::Int64(b)) => a == b,
+            (Self::Rational(a1, a2), Self::Rational(b1, b2)) => a1 == b1 && a2 == b2,
+            (Self::Float64(a), Self::Float64(b)) => a.to_bits() == b.to_bits(),
+            (Self::Bool(a), Self::Bool(b)) => a == b,
+            (Self::Char(a), Self::Char(b)) => a == b,
+            (Self::Vector(a), Self::Vector(b)) => a == b,
+            (Self::Port(a), Self::Port(b)) => a.is_same(b),
+            _ => false,
+        }
+    }
+}
+
+impl Eq for Object {}
+
 // Freeing reference cycles is a future problem
 
 #[derive(Clone, PartialEq, Eq)]
@@ -55,10 +113,60 @@ impl Handle {
         Handle(Rc::new(RefCell::new(Object::Int64(value))))
     }
 
+    /// Builds an exact rational `numerator/denominator`, reduced to lowest
+    /// terms with a positive denominator. Collapses to an `Int64` when the
+    /// result is a whole number.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `denominator` is zero; callers must reject that case (e.g.
+    /// as a `ParseError`) before constructing a rational.
+    pub fn new_rational(mut numerator: i64, mut denominator: i64) -> Self {
+        assert!(denominator != 0, "rational with zero denominator");
+        if denominator < 0 {
+            numerator = -numerator;
+            denominator = -denominator;
+        }
+        let divisor = gcd(numerator.unsigned_abs(), denominator.unsigned_abs()).max(1);
+        let divisor =
+            i64::try_from(divisor).expect("divisor cannot exceed either operand's magnitude");
+        numerator /= divisor;
+        denominator /= divisor;
+        if denominator == 1 {
+            Self::new_int64(numerator)
+        } else {
+            Handle(Rc::new(RefCell::new(Object::Rational(numerator, denominator))))
+        }
+    }
+
+    pub fn new_float64(value: f64) -> Self {
+        Handle(Rc::new(RefCell::new(Object::Float64(value))))
+    }
+
     pub fn new_string(value: String) -> Self {
         Handle(Rc::new(RefCell::new(Object::String(value))))
     }
 
+    pub fn new_bool(value: bool) -> Self {
+        Handle(Rc::new(RefCell::new(Object::Bool(value))))
+    }
+
+    pub fn new_char(value: char) -> Self {
+        Handle(Rc::new(RefCell::new(Object::Char(value))))
+    }
+
+    pub fn new_vector(value: Vec<Handle>) -> Self {
+        Handle(Rc::new(RefCell::new(Object::Vector(value))))
+    }
+
+    pub fn new_input_port(port: Rc<RefCell<Input<Box<dyn Read>>>>) -> Self {
+        Handle(Rc::new(RefCell::new(Object::Port(Port::Input(port)))))
+    }
+
+    pub fn new_output_port(port: Rc<RefCell<Box<dyn Write>>>) -> Self {
+        Handle(Rc::new(RefCell::new(Object::Port(Port::Output(port)))))
+    }
+
     pub fn new_eof() -> Self {
         Handle(Rc::new(RefCell::new(Object::Eof)))
     }
@@ -68,6 +176,52 @@ impl Handle {
     }
 }
 
+fn gcd(a: u64, b: u64) -> u64 {
+    if b == 0 {
+        a
+    } else {
+        gcd(b, a % b)
+    }
+}
+
+/// Formats a float the way R7RS `write` does: always with a decimal point,
+/// so it is unambiguously distinct from an exact integer.
+///
+/// Shared by `Handle`'s `Display` impl below and [`crate::printer::print`],
+/// which both need to render a float the same way.
+#[allow(clippy::float_cmp)]
+pub(crate) fn format_float(value: f64) -> String {
+    if value.is_nan() {
+        "+nan.0".to_string()
+    } else if value.is_infinite() {
+        if value > 0.0 { "+inf.0" } else { "-inf.0" }.to_string()
+    } else if value == value.trunc() {
+        format!("{value}.")
+    } else {
+        format!("{value}")
+    }
+}
+
+/// Formats a character the way R7RS `write` does: a named escape for the
+/// common control characters, otherwise the character itself.
+///
+/// Shared by `Handle`'s `Display` impl below and [`crate::printer::print`],
+/// which both need to render a character the same way.
+pub(crate) fn format_char(value: char) -> String {
+    match value {
+        ' ' => "#\\space".to_string(),
+        '\n' => "#\\newline".to_string(),
+        '\t' => "#\\tab".to_string(),
+        '\r' => "#\\return".to_string(),
+        '\0' => "#\\null".to_string(),
+        '\u{7}' => "#\\alarm".to_string(),
+        '\u{8}' => "#\\backspace".to_string(),
+        '\u{1b}' => "#\\escape".to_string(),
+        '\u{7f}' => "#\\delete".to_string(),
+        c => format!("#\\{c}"),
+    }
+}
+
 impl Display for Handle {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match *self.borrow() {
@@ -75,7 +229,14 @@ impl Display for Handle {
             Object::Empty => write!(f, "()"),
             Object::Symbol(ref x) => write!(f, "{x}"),
             Object::Int64(x) => write!(f, "{x}"),
+            Object::Rational(n, d) => write!(f, "{n}/{d}"),
+            Object::Float64(x) => write!(f, "{}", format_float(x)),
             Object::String(ref x) => write!(f, "\"{x}\""),
+            Object::Bool(true) => write!(f, "#t"),
+            Object::Bool(false) => write!(f, "#f"),
+            Object::Char(c) => write!(f, "{}", format_char(c)),
+            Object::Vector(ref v) => write_vector(v, f),
+            Object::Port(_) => write!(f, "#<port>"),
             Object::Eof => write!(f, "#<eof>"),
         }
     }
@@ -103,3 +264,14 @@ fn write_cons(car: &Handle, cdr: &Handle, f: &mut fmt::Formatter<'_>) -> fmt::Re
     }
     write!(f, ")")
 }
+
+fn write_vector(elements: &[Handle], f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "#(")?;
+    for (i, e) in elements.iter().enumerate() {
+        if i > 0 {
+            write!(f, " ")?;
+        }
+        e.fmt(f)?;
+    }
+    write!(f, ")")
+}